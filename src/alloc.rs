@@ -0,0 +1,126 @@
+//! RAII physical memory allocation.
+//!
+//! [`Physical`][crate::Physical] and [`PhysicalSlice`] only let callers *reference* memory that
+//! already exists. [`PhysBox`] complements them with a way to *obtain* a fresh,
+//! physically-contiguous, page-aligned region to hand to DMA-capable hardware, freeing the
+//! backing allocation on drop.
+
+use crate::bindings as C;
+use crate::slice::PhysicalSlice;
+use core::ops::Deref;
+
+/// Page granularity physical allocations are rounded up to.
+const PAGE_SIZE: usize = 0x1000;
+
+#[inline]
+fn align_up(value: usize, align: usize) -> usize {
+    (value + align - 1) & !(align - 1)
+}
+
+/// Which kernel allocator pool a [`PhysBox`] was allocated from, so [`Drop`] can free it back
+/// through the matching API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pool {
+    Normal,
+    Huge,
+}
+
+/// A physically-contiguous, page-aligned allocation, freed on [`Drop`].
+///
+/// Dereferences to a [`PhysicalSlice<u8>`][PhysicalSlice] view over the whole region.
+pub struct PhysBox {
+    address: usize,
+    size: usize,
+    pool: Pool,
+    view: PhysicalSlice<'static, u8>,
+}
+
+impl PhysBox {
+    /// Allocate `size` bytes, rounded up to the page granularity.
+    ///
+    /// Returns `None` if the kernel could not satisfy the allocation.
+    #[inline]
+    pub fn new(size: usize) -> Option<PhysBox> {
+        Self::new_aligned(size, PAGE_SIZE)
+    }
+
+    /// Allocate `size` bytes aligned to `align` (which must itself be page-aligned).
+    ///
+    /// Returns `None` if the kernel could not satisfy the allocation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is not a power of two.
+    #[inline]
+    pub fn new_aligned(size: usize, align: usize) -> Option<PhysBox> {
+        assert!(align.is_power_of_two(), "align must be a power of two");
+
+        let size = align_up(size, PAGE_SIZE);
+        let align = align_up(align, PAGE_SIZE);
+
+        let address = unsafe { C::KernelAllocPhys(size as u32, align as u32) };
+        if address == 0 {
+            return None;
+        }
+
+        Some(Self {
+            address: address as usize,
+            size,
+            pool: Pool::Normal,
+            view: PhysicalSlice::from_raw_parts(address as usize, size, None),
+        })
+    }
+
+    /// Allocate `size` bytes backed by large/huge pages, where the platform supports it.
+    ///
+    /// Returns `None` if the kernel could not satisfy the allocation.
+    #[inline]
+    pub fn new_huge(size: usize) -> Option<PhysBox> {
+        let size = align_up(size, PAGE_SIZE);
+
+        let address = unsafe { C::KernelAllocPhysHuge(size as u32) };
+        if address == 0 {
+            return None;
+        }
+
+        Some(Self {
+            address: address as usize,
+            size,
+            pool: Pool::Huge,
+            view: PhysicalSlice::from_raw_parts(address as usize, size, None),
+        })
+    }
+
+    /// The physical address of the allocation.
+    #[inline]
+    pub fn address(&self) -> usize {
+        self.address
+    }
+
+    /// The size of the allocation in bytes, rounded up to the page granularity.
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+}
+
+impl Deref for PhysBox {
+    type Target = PhysicalSlice<'static, u8>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.view
+    }
+}
+
+impl Drop for PhysBox {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            match self.pool {
+                Pool::Normal => C::KernelFreePhys(self.address as u32, self.size as u32),
+                Pool::Huge => C::KernelFreePhysHuge(self.address as u32, self.size as u32),
+            }
+        };
+    }
+}
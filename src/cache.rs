@@ -0,0 +1,137 @@
+//! Explicit cache coherency control around physical copies.
+//!
+//! On this PowerPC platform a physical read can return stale RAM while dirty cache lines sit
+//! unwritten, and a physical write can be clobbered when the CPU later evicts a cache line. Plain
+//! [`Physical::read`][crate::Physical::read]/[`write`][crate::Physical::write] never flush or
+//! invalidate, so they are only safe for CPU-visible, snooped memory. [`flush`][crate::Physical::flush]
+//! and [`invalidate`][crate::Physical::invalidate], together with [`CacheMode`], make the type
+//! usable for real device DMA, mirroring the `BufferDirection` split virtio-drivers uses for
+//! to-device flushes and from-device invalidates.
+
+use crate::slice::PhysicalSlice;
+use crate::{reattach_provenance, FromBytes, IntoBytes, Physical};
+use core::ffi::c_void;
+use core::mem;
+use wut::bindings as c_wut;
+
+/// Which direction data flows across a DMA transfer, and therefore which cache maintenance is
+/// required.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheMode {
+    /// The device wrote this region; the CPU cache must be invalidated before reading it.
+    FromDevice,
+    /// The CPU wrote this region; the cache must be flushed before the device reads it.
+    ToDevice,
+    /// Both directions: invalidate before reading, flush after writing.
+    Bidirectional,
+    /// No cache maintenance; the caller has already handled coherency or the memory is snooped.
+    None,
+}
+
+impl<T> Physical<'_, T> {
+    /// Write back this location's cache line(s), making a prior CPU write visible to a device.
+    #[inline]
+    pub fn flush(&self) {
+        unsafe {
+            let vaddr = self.as_virtual_cached_ptr();
+            c_wut::DCFlushRange(vaddr as *mut c_void, mem::size_of::<T>() as u32);
+        }
+    }
+
+    /// Invalidate this location's cache line(s), so the next CPU read observes a device's write.
+    #[inline]
+    pub fn invalidate(&mut self) {
+        unsafe {
+            let vaddr = self.as_virtual_cached_ptr();
+            c_wut::DCInvalidateRange(vaddr as *mut c_void, mem::size_of::<T>() as u32);
+        }
+    }
+
+    /// Read with the cache maintenance `mode` requires beforehand.
+    #[inline]
+    pub fn read_coherent(&mut self, mode: CacheMode) -> T
+    where
+        T: FromBytes,
+    {
+        if matches!(mode, CacheMode::FromDevice | CacheMode::Bidirectional) {
+            self.invalidate();
+        }
+
+        self.read()
+    }
+
+    /// Write with the cache maintenance `mode` requires afterward.
+    #[inline]
+    pub fn write_coherent(&mut self, value: T, mode: CacheMode)
+    where
+        T: IntoBytes,
+    {
+        self.write(value);
+
+        if matches!(mode, CacheMode::ToDevice | CacheMode::Bidirectional) {
+            self.flush();
+        }
+    }
+}
+
+impl<T> PhysicalSlice<'_, T> {
+    /// Write back this view's cache line(s) with a single `DCFlushRange` call over the whole
+    /// span, making prior CPU writes visible to a device.
+    ///
+    /// Unlike flushing element-by-element via [`index`][PhysicalSlice::index] and
+    /// [`Physical::flush`], this is the one-call-per-buffer behavior DMA-sized regions need.
+    #[inline]
+    pub fn flush(&self) {
+        unsafe {
+            let effective = c_wut::__OSPhysicalToEffectiveCached(self.address() as u32) as usize;
+            let vaddr = reattach_provenance(self.source, effective);
+            c_wut::DCFlushRange(
+                vaddr as *mut c_void,
+                (self.len() * mem::size_of::<T>()) as u32,
+            );
+        }
+    }
+
+    /// Invalidate this view's cache line(s) with a single `DCInvalidateRange` call over the whole
+    /// span, so the next CPU read observes a device's write.
+    ///
+    /// Unlike invalidating element-by-element via [`index`][PhysicalSlice::index] and
+    /// [`Physical::invalidate`], this is the one-call-per-buffer behavior DMA-sized regions need.
+    #[inline]
+    pub fn invalidate(&mut self) {
+        unsafe {
+            let effective = c_wut::__OSPhysicalToEffectiveCached(self.address() as u32) as usize;
+            let vaddr = reattach_provenance(self.source, effective);
+            c_wut::DCInvalidateRange(
+                vaddr as *mut c_void,
+                (self.len() * mem::size_of::<T>()) as u32,
+            );
+        }
+    }
+
+    /// Copy this entire view into `buf` with the cache maintenance `mode` requires beforehand.
+    #[inline]
+    pub fn read_into_coherent(&mut self, buf: &mut [T], mode: CacheMode)
+    where
+        T: FromBytes,
+    {
+        if matches!(mode, CacheMode::FromDevice | CacheMode::Bidirectional) {
+            self.invalidate();
+        }
+
+        self.read_into(buf);
+    }
+
+    /// Copy `buf` into this entire view with the cache maintenance `mode` requires afterward.
+    #[inline]
+    pub fn write_from_coherent(&self, buf: &[T], mode: CacheMode)
+    where
+        T: IntoBytes,
+    {
+        self.write_from(buf);
+
+        if matches!(mode, CacheMode::ToDevice | CacheMode::Bidirectional) {
+            self.flush();
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//! Streaming reader/writer over physical memory with read-once cursor semantics.
+//!
+//! Built from a [`PhysicalSlice<u8>`][PhysicalSlice] region (cf. the Linux `UserSlice`
+//! reader/writer design): each of [`PhysicalReader`]/[`PhysicalWriter`] holds a remaining region
+//! and an advancing cursor, so callers copying variable-length structures out of physical memory
+//! no longer have to recompute addresses by hand for each field.
+//!
+//! Every byte of the source is read at most once through the cursor: once `read_raw`/`read_value`
+//! has copied a byte, later reads can never see it again, even if the underlying region's
+//! contents changed in between. This is the TOCTOU guarantee the `UserSlice` design documents —
+//! it's what stops a caller parsing another process's struct from being tricked by the region
+//! changing between a length field and its payload.
+
+use crate::slice::PhysicalSlice;
+use crate::{FromBytes, IntoBytes};
+use core::mem::{self, MaybeUninit};
+
+/// A read or write was attempted past the end of the region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow;
+
+/// A cursor that reads forward through a [`PhysicalSlice<u8>`][PhysicalSlice] region, never
+/// re-reading a byte it has already returned.
+pub struct PhysicalReader<'a> {
+    remaining: PhysicalSlice<'a, u8>,
+}
+
+impl<'a> PhysicalReader<'a> {
+    /// Create a reader starting at the beginning of `region`.
+    #[inline]
+    pub fn new(region: PhysicalSlice<'a, u8>) -> PhysicalReader<'a> {
+        Self { remaining: region }
+    }
+
+    /// Bytes left to read.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.remaining.len()
+    }
+
+    /// Copy `buf.len()` bytes from the cursor into `buf`, advancing it.
+    ///
+    /// Fails with [`Overflow`] rather than reading a short or wrapped-around amount when fewer
+    /// bytes than requested remain.
+    #[inline]
+    pub fn read_raw(&mut self, buf: &mut [u8]) -> Result<(), Overflow> {
+        let n = buf.len();
+        let taken = self.remaining.range(0, n).ok_or(Overflow)?;
+        taken.read_into(buf);
+
+        self.remaining = self
+            .remaining
+            .range(n, self.remaining.len() - n)
+            .expect("n <= self.remaining.len() was just checked above");
+
+        Ok(())
+    }
+
+    /// Read a `T` from the cursor, advancing it past `mem::size_of::<T>()` bytes.
+    #[inline]
+    pub fn read_value<T: FromBytes>(&mut self) -> Result<T, Overflow> {
+        let mut value = MaybeUninit::<T>::uninit();
+        let buf = unsafe {
+            core::slice::from_raw_parts_mut(value.as_mut_ptr() as *mut u8, mem::size_of::<T>())
+        };
+
+        self.read_raw(buf)?;
+        Ok(unsafe { value.assume_init() })
+    }
+}
+
+/// A cursor that writes forward through a [`PhysicalSlice<u8>`][PhysicalSlice] region, never
+/// re-writing a byte it has already written.
+pub struct PhysicalWriter<'a> {
+    remaining: PhysicalSlice<'a, u8>,
+}
+
+impl<'a> PhysicalWriter<'a> {
+    /// Create a writer starting at the beginning of `region`.
+    #[inline]
+    pub fn new(region: PhysicalSlice<'a, u8>) -> PhysicalWriter<'a> {
+        Self { remaining: region }
+    }
+
+    /// Bytes left to write.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.remaining.len()
+    }
+
+    /// Copy `buf` to the cursor, advancing it.
+    ///
+    /// Fails with [`Overflow`] rather than writing a short or wrapped-around amount when fewer
+    /// bytes than `buf.len()` remain.
+    #[inline]
+    pub fn write_raw(&mut self, buf: &[u8]) -> Result<(), Overflow> {
+        let n = buf.len();
+        let taken = self.remaining.range(0, n).ok_or(Overflow)?;
+        taken.write_from(buf);
+
+        self.remaining = self
+            .remaining
+            .range(n, self.remaining.len() - n)
+            .expect("n <= self.remaining.len() was just checked above");
+
+        Ok(())
+    }
+
+    /// Write a `T` to the cursor, advancing it past `mem::size_of::<T>()` bytes.
+    #[inline]
+    pub fn write_value<T: IntoBytes>(&mut self, value: T) -> Result<(), Overflow> {
+        let buf = unsafe {
+            core::slice::from_raw_parts(&value as *const T as *const u8, mem::size_of::<T>())
+        };
+
+        self.write_raw(buf)
+    }
+}
@@ -6,17 +6,33 @@
 
 #![no_std]
 
+pub mod alloc;
 pub mod bindings;
+pub mod cache;
+pub mod io;
+pub mod safety;
+pub mod slice;
 
 use bindings as C;
 use core::marker::PhantomData;
 use core::mem::{self, MaybeUninit};
 use wut::bindings as c_wut;
 
+pub use alloc::PhysBox;
+pub use cache::CacheMode;
+pub use io::{PhysicalReader, PhysicalWriter};
+pub use safety::{FromBytes, IntoBytes};
+pub use slice::PhysicalSlice;
+
 /// Access memory outside of virtual memory space.
 #[derive(Debug, Clone, Copy)]
 pub struct Physical<'a, T> {
     address: usize,
+    /// The pointer this instance was constructed from, if any, kept around so that
+    /// [as_virtual_cached_ptr][Physical::as_virtual_cached_ptr] and
+    /// [as_virtual_uncached_ptr][Physical::as_virtual_uncached_ptr] can reattach its provenance
+    /// to the translated effective address instead of casting a bare integer back to a pointer.
+    source: Option<*const T>,
     _phantom: PhantomData<&'a T>,
 }
 
@@ -38,13 +54,36 @@ impl<'a, T> Physical<'a, T> {
     /// ```
     #[inline]
     pub fn from_ref(addr: &'a T) -> Physical<'a, T> {
+        let ptr = addr as *const T;
+
+        Self {
+            address: Self::to_physical(ptr as usize),
+            source: Some(ptr),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Create a physical memory access from an already-translated physical address, optionally
+    /// carrying the provenance of the pointer it was derived from.
+    ///
+    /// Unlike [from_address][Physical::from_address] this is not restricted to the `'static`
+    /// lifetime, which lets other parts of the crate hand out narrower views (e.g. a sub-range
+    /// of a slice) that are still tied to a borrow. If `source` is `None`,
+    /// [as_virtual_cached_ptr][Physical::as_virtual_cached_ptr] and
+    /// [as_virtual_uncached_ptr][Physical::as_virtual_uncached_ptr] fall back to an
+    /// invalid-provenance pointer for instances created this way.
+    #[inline]
+    pub(crate) fn from_raw_address(address: usize, source: Option<*const T>) -> Physical<'a, T> {
         Self {
-            address: Self::to_physical(addr as *const T as usize),
+            address,
+            source,
             _phantom: PhantomData,
         }
     }
 
-    pub fn get_address(&self) -> usize {
+    /// The physical address this instance points to.
+    #[inline]
+    pub fn address(&self) -> usize {
         self.address
     }
 }
@@ -77,6 +116,7 @@ impl<T> Physical<'static, T> {
     pub fn from_ptr(ptr: *const T) -> Physical<'static, T> {
         Self {
             address: Self::to_physical(ptr as usize),
+            source: Some(ptr),
             _phantom: PhantomData,
         }
     }
@@ -85,6 +125,10 @@ impl<T> Physical<'static, T> {
     ///
     /// Prefer to use [from_ref][Physical::from_ref] or [from_ptr][Physical::from_ptr] wherever possible.
     ///
+    /// Since no originating pointer is available, [as_virtual_cached_ptr][Physical::as_virtual_cached_ptr]
+    /// and [as_virtual_uncached_ptr][Physical::as_virtual_uncached_ptr] fall back to an
+    /// invalid-provenance pointer for instances created this way.
+    ///
     /// # Safety
     ///
     /// Address must be the location of valid, properly aligned, and initialized data. While operations will still work even if these conditions are not met, the data might be incomplete or corrupted.
@@ -106,6 +150,7 @@ impl<T> Physical<'static, T> {
     pub fn from_address(physical_address: usize) -> Physical<'static, T> {
         Self {
             address: physical_address,
+            source: None,
             _phantom: PhantomData,
         }
     }
@@ -127,19 +172,84 @@ impl<T> Physical<'_, T> {
         c_wut::__OSPhysicalToEffectiveUncached(self.address as u32) as usize
     }
 
+    /// Like [as_virtual_cached][Physical::as_virtual_cached], but returns a pointer carrying the
+    /// provenance of the pointer or reference this instance was constructed from, rather than a
+    /// bare address that would need an unsound integer-to-pointer cast to dereference.
+    ///
+    /// Falls back to an invalid-provenance pointer (see [core::ptr::without_provenance]) if this
+    /// instance was constructed from a physical address rather than a pointer or reference.
+    #[inline]
+    pub unsafe fn as_virtual_cached_ptr(&self) -> *const T {
+        let effective = c_wut::__OSPhysicalToEffectiveCached(self.address as u32) as usize;
+        reattach_provenance(self.source, effective)
+    }
+
+    /// Like [as_virtual_uncached][Physical::as_virtual_uncached], but returns a pointer carrying
+    /// the provenance of the pointer or reference this instance was constructed from, rather than
+    /// a bare address that would need an unsound integer-to-pointer cast to dereference.
+    ///
+    /// Falls back to an invalid-provenance pointer (see [core::ptr::without_provenance]) if this
+    /// instance was constructed from a physical address rather than a pointer or reference.
     #[inline]
-    pub fn read(&self) -> T {
-        let value = MaybeUninit::<T>::uninit();
-        let mut ptr = Physical::from_ref(unsafe { &*value.as_ptr() });
+    pub unsafe fn as_virtual_uncached_ptr(&self) -> *const T {
+        let effective = c_wut::__OSPhysicalToEffectiveUncached(self.address as u32) as usize;
+        reattach_provenance(self.source, effective)
+    }
+
+    /// Read a `T` from physical memory.
+    ///
+    /// Bound on [`FromBytes`] because the bytes copied in may not correspond to anything the
+    /// caller wrote there; see [read_unchecked][Physical::read_unchecked] for the unbounded
+    /// version.
+    #[inline]
+    pub fn read(&self) -> T
+    where
+        T: FromBytes,
+    {
+        unsafe { self.read_unchecked() }
+    }
+
+    /// Read a `T` from physical memory without requiring [`FromBytes`].
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure the physical memory holds a valid bit pattern for `T`. This is
+    /// instant undefined behavior for types like `bool`, `char`, enums, or anything containing
+    /// references or `NonNull` if the underlying bytes do not happen to encode a valid value.
+    #[inline]
+    pub unsafe fn read_unchecked(&self) -> T {
+        let mut value = MaybeUninit::<T>::uninit();
 
         unsafe {
-            copy(self, &mut ptr, mem::size_of::<T>());
+            // Copy by raw address rather than going through `Physical::from_ref`, which would
+            // require forming a `&T` over `value` while it is still uninitialized.
+            let dst_address = Self::to_physical(value.as_mut_ptr() as usize);
+            C::KernelCopyData(dst_address as u32, self.address as u32, mem::size_of::<T>() as u32);
             value.assume_init()
         }
     }
 
+    /// Write a `T` to physical memory.
+    ///
+    /// Bound on [`IntoBytes`] because writing out `value`'s bytes as-is would otherwise leak any
+    /// padding or uninitialized bytes it holds; see [write_unchecked][Physical::write_unchecked]
+    /// for the unbounded version.
+    #[inline]
+    pub fn write(&mut self, value: T)
+    where
+        T: IntoBytes,
+    {
+        unsafe { self.write_unchecked(value) }
+    }
+
+    /// Write a `T` to physical memory without requiring [`IntoBytes`].
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure `value` has no padding or uninitialized bytes, or must accept that
+    /// those bytes are leaked into physical RAM as-is.
     #[inline]
-    pub fn write(&mut self, value: T) {
+    pub unsafe fn write_unchecked(&mut self, value: T) {
         let ptr = Physical::from_ref(&value);
 
         unsafe {
@@ -147,15 +257,43 @@ impl<T> Physical<'_, T> {
         }
     }
 
+    /// Replace the value at this physical location, returning the previous one.
     #[inline]
-    pub fn replace(&mut self, value: T) -> T {
+    pub fn replace(&mut self, value: T) -> T
+    where
+        T: FromBytes + IntoBytes,
+    {
         let prev = self.read();
         self.write(value);
         prev
     }
+
+    /// Replace the value at this physical location without requiring [`FromBytes`] +
+    /// [`IntoBytes`], returning the previous one.
+    ///
+    /// # Safety
+    ///
+    /// See [read_unchecked][Physical::read_unchecked] and [write_unchecked][Physical::write_unchecked].
+    #[inline]
+    pub unsafe fn replace_unchecked(&mut self, value: T) -> T {
+        let prev = unsafe { self.read_unchecked() };
+        unsafe { self.write_unchecked(value) };
+        prev
+    }
 }
 
 #[inline]
 pub unsafe fn copy<T>(src: &Physical<T>, dst: &mut Physical<T>, count: usize) {
     C::KernelCopyData(dst.address as u32, src.address as u32, count as u32);
 }
+
+/// Attach `effective` to the provenance of `source`, or fall back to an invalid-provenance
+/// pointer (see [core::ptr::without_provenance]) if none was recorded. Shared by
+/// [`Physical`]'s and [`PhysicalSlice`][slice::PhysicalSlice]'s `as_virtual_*_ptr`-style methods.
+#[inline]
+pub(crate) fn reattach_provenance<T>(source: Option<*const T>, effective: usize) -> *const T {
+    match source {
+        Some(ptr) => ptr.with_addr(effective),
+        None => core::ptr::without_provenance(effective),
+    }
+}
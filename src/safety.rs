@@ -0,0 +1,42 @@
+//! Safety traits bounding which types may be reconstructed from, or reinterpreted as, raw bytes.
+//!
+//! Modeled after zerocopy: [`FromBytes`] means every bit pattern is a valid `T`, so it is sound to
+//! materialize one from bytes copied out of physical memory; [`IntoBytes`] means `T` has no
+//! padding or uninitialized bytes, so it is sound to reinterpret one as bytes. Types like `bool`,
+//! `char`, enums, or anything containing references or `NonNull` do not satisfy [`FromBytes`] and
+//! must not implement it; this module implements neither trait for them.
+
+/// Marker for types where every bit pattern is a valid instance.
+///
+/// # Safety
+///
+/// Implementors must be valid for any combination of bits. Implementing this for a type with
+/// invalid bit patterns (`bool`, `char`, enums with a restricted discriminant, references,
+/// `NonNull`, ...) is instant undefined behavior the moment [`Physical::read`][crate::Physical::read]
+/// materializes one from arbitrary physical memory.
+pub unsafe trait FromBytes {}
+
+/// Marker for types with no padding or uninitialized bytes.
+///
+/// # Safety
+///
+/// Implementors must be fully initialized for every value a safe caller can construct, with no
+/// padding bytes. Implementing this for a type that can hold uninitialized bytes lets
+/// [`Physical::write`][crate::Physical::write] leak them into physical RAM.
+pub unsafe trait IntoBytes {}
+
+macro_rules! impl_bytes_for_primitives {
+    ($($t:ty),* $(,)?) => {
+        $(
+            unsafe impl FromBytes for $t {}
+            unsafe impl IntoBytes for $t {}
+        )*
+    };
+}
+
+impl_bytes_for_primitives!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64
+);
+
+unsafe impl<T: FromBytes, const N: usize> FromBytes for [T; N] {}
+unsafe impl<T: IntoBytes, const N: usize> IntoBytes for [T; N] {}
@@ -0,0 +1,174 @@
+//! Slice-level physical access.
+//!
+//! [`Physical`][crate::Physical] models a single value; [`PhysicalSlice`] extends the same idea
+//! to a contiguous run of values so that large buffers can be copied out of (or into) another
+//! application's address space with a single [`KernelCopyData`][crate::bindings::KernelCopyData]
+//! call instead of one call per element.
+
+use crate::bindings as C;
+use crate::{FromBytes, IntoBytes, Physical};
+use core::marker::PhantomData;
+use core::mem;
+
+/// A bounds-checked view over a contiguous run of `T` in physical memory.
+#[derive(Debug, Clone, Copy)]
+pub struct PhysicalSlice<'a, T> {
+    address: usize,
+    len: usize,
+    /// The pointer this view was constructed from, if any. Carried through [`index`][Self::index]
+    /// and [`range`][Self::range] so that `Physical`'s `as_virtual_*_ptr` methods can reattach
+    /// real provenance instead of always falling back to an invalid-provenance pointer; see
+    /// [`Physical::from_raw_address`][crate::Physical::from_raw_address]. `pub(crate)` so
+    /// [`cache`][crate::cache] can reattach the same provenance for bulk flush/invalidate.
+    pub(crate) source: Option<*const T>,
+    _phantom: PhantomData<&'a [T]>,
+}
+
+impl<'a, T> PhysicalSlice<'a, T> {
+    /// Create a physical slice access from a reference to a slice.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let xs = [1, 2, 3];
+    /// let slice = PhysicalSlice::from_slice(&xs);
+    /// ```
+    #[inline]
+    pub fn from_slice(slice: &'a [T]) -> PhysicalSlice<'a, T> {
+        let ptr = slice.as_ptr();
+
+        Self {
+            address: Physical::<T>::to_physical(ptr as usize),
+            len: slice.len(),
+            source: Some(ptr),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Create a physical slice access from an already-translated physical address and length,
+    /// optionally carrying the provenance of the pointer it was derived from.
+    #[inline]
+    pub(crate) fn from_raw_parts(
+        address: usize,
+        len: usize,
+        source: Option<*const T>,
+    ) -> PhysicalSlice<'a, T> {
+        Self {
+            address,
+            len,
+            source,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Number of elements covered by this view.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether this view covers zero elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The physical address this view starts at.
+    #[inline]
+    pub fn address(&self) -> usize {
+        self.address
+    }
+
+    /// A single-element view at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= self.len()`, mirroring slice indexing.
+    #[inline]
+    pub fn index(&self, index: usize) -> Physical<'a, T> {
+        assert!(index < self.len, "index out of bounds of physical slice");
+        Physical::from_raw_address(
+            self.address + index * mem::size_of::<T>(),
+            self.source.map(|ptr| ptr.wrapping_add(index)),
+        )
+    }
+
+    /// A narrower view over `offset..offset + n`.
+    ///
+    /// Returns `None` if the requested range exceeds `self.len()`.
+    #[inline]
+    pub fn range(&self, offset: usize, n: usize) -> Option<PhysicalSlice<'a, T>> {
+        if offset.checked_add(n)? > self.len {
+            return None;
+        }
+
+        Some(Self {
+            address: self.address + offset * mem::size_of::<T>(),
+            len: n,
+            source: self.source.map(|ptr| ptr.wrapping_add(offset)),
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Copy this entire view into `buf` with a single [`KernelCopyData`][C::KernelCopyData] call.
+    ///
+    /// Bound on [`FromBytes`] for the same reason as [`Physical::read`]: the copied bytes may not
+    /// correspond to anything the caller wrote there.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buf.len() != self.len()`.
+    #[inline]
+    pub fn read_into(&self, buf: &mut [T])
+    where
+        T: FromBytes,
+    {
+        assert_eq!(
+            buf.len(),
+            self.len,
+            "buffer length does not match physical slice length"
+        );
+
+        unsafe {
+            // `buf` lives at a virtual address; `KernelCopyData` expects two physical ones, so
+            // translate it the same way `Physical::read_unchecked` does.
+            let dst_address = Physical::<T>::to_physical(buf.as_mut_ptr() as usize);
+            C::KernelCopyData(
+                dst_address as u32,
+                self.address as u32,
+                (self.len * mem::size_of::<T>()) as u32,
+            );
+        }
+    }
+
+    /// Copy `buf` into this entire view with a single [`KernelCopyData`][C::KernelCopyData] call.
+    ///
+    /// Bound on [`IntoBytes`] for the same reason as [`Physical::write`]: writing `buf`'s bytes
+    /// as-is would otherwise leak any padding or uninitialized bytes it holds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buf.len() != self.len()`.
+    #[inline]
+    pub fn write_from(&self, buf: &[T])
+    where
+        T: IntoBytes,
+    {
+        assert_eq!(
+            buf.len(),
+            self.len,
+            "buffer length does not match physical slice length"
+        );
+
+        unsafe {
+            // `buf` lives at a virtual address; `KernelCopyData` expects two physical ones, so
+            // translate it the same way `Physical::read_unchecked` does.
+            let src_address = Physical::<T>::to_physical(buf.as_ptr() as usize);
+            C::KernelCopyData(
+                self.address as u32,
+                src_address as u32,
+                (self.len * mem::size_of::<T>()) as u32,
+            );
+        }
+    }
+}